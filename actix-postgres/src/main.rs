@@ -1,11 +1,15 @@
+use actix::{Actor, Addr, AsyncContext, Handler, Message, StreamHandler};
 use actix_web::middleware::Logger;
 use actix_web::{error, http::StatusCode, Result};
-use actix_web::{web, App, HttpResponse, HttpServer};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use actix_web_actors::ws;
 use chrono::{Local, NaiveDateTime};
 use dotenv::dotenv;
 use serde::{Deserialize, Serialize};
+use serde_json::Error as JsonError;
 use sqlx::error::Error as SQLxError;
 use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
 use std::io::Write;
@@ -16,14 +20,33 @@ pub enum MyError {
     DBError(String),
     ActixError(String),
     NotFound(String),
+    InvalidInput(String),
+    SerdeError(String),
+    IoError(String),
+    ServiceUnavailable(String),
 }
 
 #[derive(Debug, Serialize)]
 pub struct MyErrorResponse {
+    status_code: u16,
+    error_type: String,
     error_msg: String,
+    details: Option<Vec<String>>,
 }
 
 impl MyError {
+    fn error_type(&self) -> &'static str {
+        match self {
+            MyError::DBError(_msg) => "db_error",
+            MyError::ActixError(_msg) => "actix_error",
+            MyError::NotFound(_msg) => "not_found",
+            MyError::InvalidInput(_msg) => "invalid_input",
+            MyError::SerdeError(_msg) => "serde_error",
+            MyError::IoError(_msg) => "io_error",
+            MyError::ServiceUnavailable(_msg) => "service_unavailable",
+        }
+    }
+
     fn error_response(&self) -> String {
         match self {
             MyError::DBError(msg) => {
@@ -38,6 +61,31 @@ impl MyError {
                 println!("Not found error occurred:{:?}", msg);
                 msg.into()
             }
+            MyError::InvalidInput(msg) => {
+                println!("Invalid input error occurred:{:?}", msg);
+                msg.into()
+            }
+            MyError::SerdeError(msg) => {
+                println!("Serialization error occurred:{:?}", msg);
+                "Invalid JSON payload".into()
+            }
+            MyError::IoError(msg) => {
+                println!("I/O error occurred:{:?}", msg);
+                "Internal server error".into()
+            }
+            MyError::ServiceUnavailable(msg) => {
+                println!("Service unavailable:{:?}", msg);
+                "Service unavailable".into()
+            }
+        }
+    }
+
+    fn details(&self) -> Option<Vec<String>> {
+        match self {
+            MyError::InvalidInput(msg) => {
+                Some(msg.split(';').map(|field| field.trim().to_string()).collect())
+            }
+            _ => None,
         }
     }
 }
@@ -45,14 +93,22 @@ impl MyError {
 impl error::ResponseError for MyError {
     fn status_code(&self) -> StatusCode {
         match self {
-            MyError::DBError(_msg) | MyError::ActixError(_msg) => StatusCode::INTERNAL_SERVER_ERROR,
+            MyError::DBError(_msg) | MyError::ActixError(_msg) | MyError::IoError(_msg) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
             MyError::NotFound(_msg) => StatusCode::NOT_FOUND,
+            MyError::InvalidInput(_msg) | MyError::SerdeError(_msg) => StatusCode::BAD_REQUEST,
+            MyError::ServiceUnavailable(_msg) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        HttpResponse::build(self.status_code()).json(MyErrorResponse {
+        let status_code = self.status_code();
+        HttpResponse::build(status_code).json(MyErrorResponse {
+            status_code: status_code.as_u16(),
+            error_type: self.error_type().into(),
             error_msg: self.error_response(),
+            details: self.details(),
         })
     }
 }
@@ -75,12 +131,30 @@ impl From<SQLxError> for MyError {
     }
 }
 
+impl From<JsonError> for MyError {
+    fn from(err: JsonError) -> Self {
+        MyError::SerdeError(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for MyError {
+    fn from(err: std::io::Error) -> Self {
+        MyError::IoError(err.to_string())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Course {
     pub teacher_id: i32,
     pub id: Option<i32>,
     pub name: String,
     pub time: Option<NaiveDateTime>,
+    pub description: Option<String>,
+    pub format: Option<String>,
+    pub structure: Option<String>,
+    pub duration: Option<String>,
+    pub price: Option<i32>,
+    pub language: Option<String>,
 }
 
 impl From<web::Json<Course>> for Course {
@@ -90,6 +164,83 @@ impl From<web::Json<Course>> for Course {
             id: course.id,
             name: course.name.clone(),
             time: course.time,
+            description: course.description.clone(),
+            format: course.format.clone(),
+            structure: course.structure.clone(),
+            duration: course.duration.clone(),
+            price: course.price,
+            language: course.language.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Teacher {
+    pub id: i32,
+    pub name: String,
+    pub picture_url: String,
+    pub profile: String,
+}
+
+impl From<web::Json<Teacher>> for Teacher {
+    fn from(teacher: web::Json<Teacher>) -> Self {
+        Teacher {
+            id: teacher.id,
+            name: teacher.name.clone(),
+            picture_url: teacher.picture_url.clone(),
+            profile: teacher.profile.clone(),
+        }
+    }
+}
+
+const MAX_NAME_LENGTH: usize = 140;
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), MyError>;
+}
+
+impl Validate for Course {
+    fn validate(&self) -> Result<(), MyError> {
+        let mut errors = vec![];
+
+        let name = self.name.trim();
+        if name.is_empty() {
+            errors.push("name: must not be empty".to_string());
+        } else if name.chars().count() > MAX_NAME_LENGTH {
+            errors.push(format!("name: must be at most {} characters", MAX_NAME_LENGTH));
+        }
+
+        if self.price.map_or(false, |price| price < 0) {
+            errors.push("price: must not be negative".to_string());
+        }
+
+        if self.time.map_or(false, |time| time > Local::now().naive_local()) {
+            errors.push("time: must not be in the future".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MyError::InvalidInput(errors.join("; ")))
+        }
+    }
+}
+
+impl Validate for Teacher {
+    fn validate(&self) -> Result<(), MyError> {
+        let mut errors = vec![];
+
+        let name = self.name.trim();
+        if name.is_empty() {
+            errors.push("name: must not be empty".to_string());
+        } else if name.chars().count() > MAX_NAME_LENGTH {
+            errors.push(format!("name: must be at most {} characters", MAX_NAME_LENGTH));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MyError::InvalidInput(errors.join("; ")))
         }
     }
 }
@@ -99,24 +250,147 @@ pub struct AppState {
     pub visit_count: Mutex<u32>,
     // pub courses: Mutex<Vec<Course>>,
     pub postgres: PgPool,
+    pub teacher_sessions: Mutex<HashMap<i32, Vec<Addr<TeacherNotificationSession>>>>,
 }
 
-pub async fn health_check_handler(app_state: web::Data<AppState>) -> HttpResponse {
-    let health_check_response = &app_state.health_check_response;
-    let mut visit_count = app_state.visit_count.lock().unwrap();
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct CourseNotification(pub String);
+
+pub struct TeacherNotificationSession {
+    pub teacher_id: i32,
+    pub app_state: web::Data<AppState>,
+}
+
+impl Actor for TeacherNotificationSession {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.app_state
+            .teacher_sessions
+            .lock()
+            .unwrap()
+            .entry(self.teacher_id)
+            .or_insert_with(Vec::new)
+            .push(ctx.address());
+    }
+
+    fn stopped(&mut self, ctx: &mut Self::Context) {
+        let mut sessions = self.app_state.teacher_sessions.lock().unwrap();
+        if let Some(addrs) = sessions.get_mut(&self.teacher_id) {
+            addrs.retain(|addr| addr != &ctx.address());
+            if addrs.is_empty() {
+                sessions.remove(&self.teacher_id);
+            }
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for TeacherNotificationSession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Handler<CourseNotification> for TeacherNotificationSession {
+    type Result = ();
+
+    fn handle(&mut self, msg: CourseNotification, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+pub async fn teacher_ws(
+    req: HttpRequest,
+    stream: web::Payload,
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32)>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let teacher_id = params.into_inner();
+
+    ws::start(
+        TeacherNotificationSession {
+            teacher_id,
+            app_state,
+        },
+        &req,
+        stream,
+    )
+}
+
+#[derive(Debug, Serialize)]
+pub struct CourseChangeEvent<'a> {
+    pub event: &'a str,
+    pub course: &'a Course,
+}
+
+fn broadcast_course_change(
+    app_state: &web::Data<AppState>,
+    teacher_id: i32,
+    event: &str,
+    course: &Course,
+) {
+    let sessions = app_state.teacher_sessions.lock().unwrap();
+    if let Some(addrs) = sessions.get(&teacher_id) {
+        let payload = serde_json::to_string(&CourseChangeEvent { event, course }).unwrap_or_default();
+        for addr in addrs {
+            addr.do_send(CourseNotification(payload.clone()));
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthCheckResponse {
+    pub message: String,
+    pub visit_count: u32,
+    pub pool_size: u32,
+    pub pool_idle: u32,
+    pub pool_in_use: u32,
+}
+
+pub async fn health_check_handler(
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse, MyError> {
+    sqlx::query("SELECT 1")
+        .execute(&app_state.postgres)
+        .await
+        .map_err(|err| MyError::ServiceUnavailable(err.to_string()))?;
 
-    let response = format!("{} {} times", health_check_response, visit_count);
+    let mut visit_count = app_state.visit_count.lock().unwrap();
+    let count = *visit_count;
+    let message = format!("{} {} times", &app_state.health_check_response, count);
     *visit_count += 1;
-    HttpResponse::Ok().json(&response)
+
+    let pool_size = app_state.postgres.size();
+    let pool_idle = app_state.postgres.num_idle() as u32;
+
+    Ok(HttpResponse::Ok().json(HealthCheckResponse {
+        message,
+        visit_count: count,
+        pool_size,
+        pool_idle,
+        pool_in_use: pool_size.saturating_sub(pool_idle),
+    }))
 }
 
 pub async fn new_course(
     new_course: web::Json<Course>,
     app_state: web::Data<AppState>,
 ) -> Result<HttpResponse, MyError> {
-    post_new_course_db(&app_state.postgres, new_course.into())
-        .await
-        .map(|course| HttpResponse::Ok().json(course))
+    new_course.validate()?;
+    teacher_exists_db(&app_state.postgres, new_course.teacher_id).await?;
+
+    let course = post_new_course_db(&app_state.postgres, new_course.into()).await?;
+    broadcast_course_change(&app_state, course.teacher_id, "created", &course);
+
+    Ok(HttpResponse::Ok().json(course))
 }
 
 pub async fn get_courses_for_teacher_db(
@@ -124,7 +398,7 @@ pub async fn get_courses_for_teacher_db(
     teacher_id: i32,
 ) -> Result<Vec<Course>, MyError> {
     let rows = sqlx::query!(
-        r#"SELECT id, teacher_id, name, time 
+        r#"SELECT id, teacher_id, name, time, description, format, structure, duration, price, language
         FROM course
         WHERE teacher_id = $1"#,
         teacher_id
@@ -139,6 +413,12 @@ pub async fn get_courses_for_teacher_db(
             teacher_id: r.teacher_id,
             name: r.name.clone(),
             time: Some(NaiveDateTime::from(r.time.unwrap())),
+            description: r.description.clone(),
+            format: r.format.clone(),
+            structure: r.structure.clone(),
+            duration: r.duration.clone(),
+            price: r.price,
+            language: r.language.clone(),
         })
         .collect();
 
@@ -148,32 +428,50 @@ pub async fn get_courses_for_teacher_db(
     }
 }
 
-pub async fn get_course_details_db(pool: &PgPool, teacher_id: i32, course_id: i32) -> Course {
+pub async fn get_course_details_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    course_id: i32,
+) -> Result<Course, MyError> {
     let row = sqlx::query!(
-        r#"select id, teacher_id, name, time from course
+        r#"select id, teacher_id, name, time, description, format, structure, duration, price, language
+        from course
         where teacher_id = $1 and id = $2"#,
         teacher_id,
         course_id
     )
-    .fetch_one(pool)
-    .await
-    .unwrap();
+    .fetch_optional(pool)
+    .await?;
 
-    Course {
+    row.map(|row| Course {
         id: Some(row.id),
         teacher_id: row.teacher_id,
         name: row.name.clone(),
         time: Some(NaiveDateTime::from(row.time.unwrap())),
-    }
+        description: row.description,
+        format: row.format,
+        structure: row.structure,
+        duration: row.duration,
+        price: row.price,
+        language: row.language,
+    })
+    .ok_or_else(|| MyError::NotFound("Course not found".into()))
 }
 
 pub async fn post_new_course_db(pool: &PgPool, new_course: Course) -> Result<Course, MyError> {
     let row = sqlx::query!(
-        r#"insert into course( id, teacher_id, name )
-        values($1,$2,$3) returning id, teacher_id, name, time"#,
-        new_course.id,
+        r#"insert into course( teacher_id, name, time, description, format, structure, duration, price, language )
+        values($1,$2,COALESCE($3, now()),$4,$5,$6,$7,$8,$9)
+        returning id, teacher_id, name, time, description, format, structure, duration, price, language"#,
         new_course.teacher_id,
         new_course.name,
+        new_course.time,
+        new_course.description,
+        new_course.format,
+        new_course.structure,
+        new_course.duration,
+        new_course.price,
+        new_course.language,
     )
     .fetch_one(pool)
     .await?;
@@ -183,7 +481,243 @@ pub async fn post_new_course_db(pool: &PgPool, new_course: Course) -> Result<Cou
         teacher_id: row.teacher_id,
         name: row.name.clone(),
         time: Some(NaiveDateTime::from(row.time.unwrap())),
+        description: row.description,
+        format: row.format,
+        structure: row.structure,
+        duration: row.duration,
+        price: row.price,
+        language: row.language,
+    })
+}
+
+pub async fn update_course_details_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    course_id: i32,
+    updated_course: Course,
+) -> Result<Course, MyError> {
+    let existing = get_course_details_db(pool, teacher_id, course_id).await?;
+
+    let name = updated_course.name;
+    let time = updated_course.time.or(existing.time);
+    let description = updated_course.description.or(existing.description);
+    let format = updated_course.format.or(existing.format);
+    let structure = updated_course.structure.or(existing.structure);
+    let duration = updated_course.duration.or(existing.duration);
+    let price = updated_course.price.or(existing.price);
+    let language = updated_course.language.or(existing.language);
+
+    let row = sqlx::query!(
+        r#"UPDATE course SET name = $1, time = $2, description = $3, format = $4, structure = $5,
+        duration = $6, price = $7, language = $8
+        WHERE teacher_id = $9 AND id = $10
+        RETURNING id, teacher_id, name, time, description, format, structure, duration, price, language"#,
+        name,
+        time,
+        description,
+        format,
+        structure,
+        duration,
+        price,
+        language,
+        teacher_id,
+        course_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(|row| Course {
+        id: Some(row.id),
+        teacher_id: row.teacher_id,
+        name: row.name.clone(),
+        time: Some(NaiveDateTime::from(row.time.unwrap())),
+        description: row.description,
+        format: row.format,
+        structure: row.structure,
+        duration: row.duration,
+        price: row.price,
+        language: row.language,
     })
+    .ok_or_else(|| MyError::NotFound("Course not found".into()))
+}
+
+pub async fn delete_course_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    course_id: i32,
+) -> Result<String, MyError> {
+    let rows_affected = sqlx::query!(
+        r#"DELETE FROM course WHERE teacher_id = $1 AND id = $2"#,
+        teacher_id,
+        course_id
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    match rows_affected {
+        0 => Err(MyError::NotFound("Course not found".into())),
+        _ => Ok(format!("Deleted {} course(s)", rows_affected)),
+    }
+}
+
+pub async fn get_all_teachers_db(pool: &PgPool) -> Result<Vec<Teacher>, MyError> {
+    let rows = sqlx::query!(r#"SELECT id, name, picture_url, profile FROM teacher"#)
+        .fetch_all(pool)
+        .await?;
+
+    let teachers: Vec<Teacher> = rows
+        .iter()
+        .map(|r| Teacher {
+            id: r.id,
+            name: r.name.clone(),
+            picture_url: r.picture_url.clone(),
+            profile: r.profile.clone(),
+        })
+        .collect();
+
+    Ok(teachers)
+}
+
+pub async fn get_teacher_details_db(pool: &PgPool, teacher_id: i32) -> Result<Teacher, MyError> {
+    let row = sqlx::query!(
+        r#"SELECT id, name, picture_url, profile FROM teacher
+        WHERE id = $1"#,
+        teacher_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(|r| Teacher {
+        id: r.id,
+        name: r.name,
+        picture_url: r.picture_url,
+        profile: r.profile,
+    })
+    .ok_or_else(|| MyError::NotFound("Teacher not found".into()))
+}
+
+pub async fn teacher_exists_db(pool: &PgPool, teacher_id: i32) -> Result<(), MyError> {
+    let row = sqlx::query!(r#"SELECT id FROM teacher WHERE id = $1"#, teacher_id)
+        .fetch_optional(pool)
+        .await?;
+
+    match row {
+        Some(_) => Ok(()),
+        None => Err(MyError::InvalidInput(format!(
+            "teacher_id: no teacher with id {}",
+            teacher_id
+        ))),
+    }
+}
+
+pub async fn post_new_teacher_db(pool: &PgPool, new_teacher: Teacher) -> Result<Teacher, MyError> {
+    let row = sqlx::query!(
+        r#"INSERT INTO teacher ( name, picture_url, profile )
+        VALUES($1, $2, $3) RETURNING id, name, picture_url, profile"#,
+        new_teacher.name,
+        new_teacher.picture_url,
+        new_teacher.profile,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Teacher {
+        id: row.id,
+        name: row.name,
+        picture_url: row.picture_url,
+        profile: row.profile,
+    })
+}
+
+pub async fn update_teacher_details_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    updated_teacher: Teacher,
+) -> Result<Teacher, MyError> {
+    let row = sqlx::query!(
+        r#"UPDATE teacher SET name = $1, picture_url = $2, profile = $3
+        WHERE id = $4 RETURNING id, name, picture_url, profile"#,
+        updated_teacher.name,
+        updated_teacher.picture_url,
+        updated_teacher.profile,
+        teacher_id,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(|r| Teacher {
+        id: r.id,
+        name: r.name,
+        picture_url: r.picture_url,
+        profile: r.profile,
+    })
+    .ok_or_else(|| MyError::NotFound("Teacher not found".into()))
+}
+
+pub async fn delete_teacher_db(pool: &PgPool, teacher_id: i32) -> Result<String, MyError> {
+    let rows_affected = sqlx::query!(r#"DELETE FROM teacher WHERE id = $1"#, teacher_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    match rows_affected {
+        0 => Err(MyError::NotFound("Teacher not found".into())),
+        _ => Ok(format!("Deleted {} teacher(s)", rows_affected)),
+    }
+}
+
+pub async fn get_all_teachers(app_state: web::Data<AppState>) -> Result<HttpResponse, MyError> {
+    get_all_teachers_db(&app_state.postgres)
+        .await
+        .map(|teachers| HttpResponse::Ok().json(teachers))
+}
+
+pub async fn get_teacher_detail(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32)>,
+) -> Result<HttpResponse, MyError> {
+    let teacher_id = params.into_inner();
+
+    get_teacher_details_db(&app_state.postgres, teacher_id)
+        .await
+        .map(|teacher| HttpResponse::Ok().json(teacher))
+}
+
+pub async fn new_teacher(
+    new_teacher: web::Json<Teacher>,
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse, MyError> {
+    new_teacher.validate()?;
+
+    post_new_teacher_db(&app_state.postgres, new_teacher.into())
+        .await
+        .map(|teacher| HttpResponse::Ok().json(teacher))
+}
+
+pub async fn update_teacher(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32)>,
+    updated_teacher: web::Json<Teacher>,
+) -> Result<HttpResponse, MyError> {
+    updated_teacher.validate()?;
+
+    let teacher_id = params.into_inner();
+
+    update_teacher_details_db(&app_state.postgres, teacher_id, updated_teacher.into())
+        .await
+        .map(|teacher| HttpResponse::Ok().json(teacher))
+}
+
+pub async fn delete_teacher(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32)>,
+) -> Result<HttpResponse, MyError> {
+    let teacher_id = params.into_inner();
+
+    delete_teacher_db(&app_state.postgres, teacher_id)
+        .await
+        .map(|msg| HttpResponse::Ok().json(msg))
 }
 
 pub async fn get_courses_for_teacher(
@@ -200,12 +734,44 @@ pub async fn get_courses_for_teacher(
 pub async fn get_course_detail(
     app_state: web::Data<AppState>,
     params: web::Path<(i32, i32)>,
-) -> HttpResponse {
+) -> Result<HttpResponse, MyError> {
     let (teacher_id, course_id) = params.into_inner();
 
-    let course = get_course_details_db(&app_state.postgres, teacher_id, course_id).await;
+    get_course_details_db(&app_state.postgres, teacher_id, course_id)
+        .await
+        .map(|course| HttpResponse::Ok().json(course))
+}
+
+pub async fn update_course(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32, i32)>,
+    updated_course: web::Json<Course>,
+) -> Result<HttpResponse, MyError> {
+    updated_course.validate()?;
 
-    HttpResponse::Ok().json(course)
+    let (teacher_id, course_id) = params.into_inner();
+
+    // update_course_details_db fetches the existing row for (teacher_id, course_id) first,
+    // which already confirms the path's teacher/course pair exists.
+    let course =
+        update_course_details_db(&app_state.postgres, teacher_id, course_id, updated_course.into())
+            .await?;
+    broadcast_course_change(&app_state, teacher_id, "updated", &course);
+
+    Ok(HttpResponse::Ok().json(course))
+}
+
+pub async fn delete_course(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, MyError> {
+    let (teacher_id, course_id) = params.into_inner();
+
+    let course = get_course_details_db(&app_state.postgres, teacher_id, course_id).await?;
+    let msg = delete_course_db(&app_state.postgres, teacher_id, course_id).await?;
+    broadcast_course_change(&app_state, teacher_id, "deleted", &course);
+
+    Ok(HttpResponse::Ok().json(msg))
 }
 
 // 配置route
@@ -219,7 +785,28 @@ pub fn course_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/courses")
             .route("/", web::post().to(new_course))
             .route("/{user_id}", web::get().to(get_courses_for_teacher))
-            .route("/{user_id}/{course_id}", web::get().to(get_course_detail)),
+            .route("/{user_id}/{course_id}", web::get().to(get_course_detail))
+            .route("/{user_id}/{course_id}", web::put().to(update_course))
+            .route("/{user_id}/{course_id}", web::delete().to(delete_course)),
+    );
+}
+
+// 配置范围route
+pub fn teacher_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/teachers")
+            .route("", web::get().to(get_all_teachers))
+            .route("", web::post().to(new_teacher))
+            .route("/{id}", web::get().to(get_teacher_detail))
+            .route("/{id}", web::put().to(update_teacher))
+            .route("/{id}", web::delete().to(delete_teacher)),
+    );
+}
+
+// 配置范围route
+pub fn ws_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/ws/teachers").route("/{teacher_id}", web::get().to(teacher_ws)),
     );
 }
 
@@ -246,13 +833,23 @@ async fn main() -> std::io::Result<()> {
 
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
 
-    let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+    let max_db_connections: u32 = env::var("MAX_DB_CONNECTIONS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(5);
+
+    let db_pool = PgPoolOptions::new()
+        .max_connections(max_db_connections)
+        .connect(&database_url)
+        .await
+        .unwrap();
 
     let shared_data = web::Data::new(AppState {
         health_check_response: "I'm OK.".to_string(),
         visit_count: Mutex::new(0),
         // courses: Mutex::new(vec![]),
         postgres: db_pool,
+        teacher_sessions: Mutex::new(HashMap::new()),
     });
 
     let app = move || {
@@ -260,11 +857,16 @@ async fn main() -> std::io::Result<()> {
             .app_data(shared_data.clone())
             .configure(general_routes)
             .configure(course_routes)
+            .configure(teacher_routes)
+            .configure(ws_routes)
             .wrap(Logger::default())
         // .wrap(Logger::new("%a %{User-Agent}i"));
     };
 
-    HttpServer::new(app).bind("127.0.0.1:3000")?.run().await
+    let host = env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let port = env::var("PORT").unwrap_or_else(|_| "3000".to_string());
+
+    HttpServer::new(app).bind(format!("{}:{}", host, port))?.run().await
 }
 
 #[cfg(test)]
@@ -284,6 +886,7 @@ mod tests {
             health_check_response: "".to_string(),
             visit_count: Mutex::new(0),
             postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
         });
 
         let course = web::Json(Course {
@@ -291,12 +894,73 @@ mod tests {
             name: "Test course".into(),
             id: Some(4),
             time: None,
+            description: None,
+            format: None,
+            structure: None,
+            duration: None,
+            price: None,
+            language: None,
         });
 
         let resp = new_course(course, app_state).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[ignore]
+    #[actix_rt::test]
+    async fn update_course_test() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let updated_course = web::Json(Course {
+            teacher_id: 1,
+            name: "Updated course".into(),
+            id: None,
+            time: None,
+            description: None,
+            format: None,
+            structure: None,
+            duration: None,
+            price: Some(99),
+            language: None,
+        });
+
+        let params: web::Path<(i32, i32)> = web::Path::from((1, 4));
+        let resp = update_course(app_state, params, updated_course)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ignore]
+    #[actix_rt::test]
+    async fn delete_course_test() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let params: web::Path<(i32, i32)> = web::Path::from((1, 4));
+        let resp = delete_course(app_state, params).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[actix_rt::test]
     async fn get_all_courses_success() {
         dotenv().ok();
@@ -308,6 +972,7 @@ mod tests {
             health_check_response: "".to_string(),
             visit_count: Mutex::new(0),
             postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
         });
 
         let teacher_id: web::Path<(i32)> = web::Path::from((1));
@@ -329,10 +994,124 @@ mod tests {
             health_check_response: "".to_string(),
             visit_count: Mutex::new(0),
             postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
         });
 
         let params: web::Path<(i32, i32)> = web::Path::from((1, 1));
-        let resp = get_course_detail(app_state, params).await;
+        let resp = get_course_detail(app_state, params).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ignore]
+    #[actix_rt::test]
+    async fn post_teacher_test() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let teacher = web::Json(Teacher {
+            id: 1,
+            name: "Test teacher".into(),
+            picture_url: "http://example.com/pic.png".into(),
+            profile: "A test teacher profile".into(),
+        });
+
+        let resp = new_teacher(teacher, app_state).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ignore]
+    #[actix_rt::test]
+    async fn update_teacher_test() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let updated_teacher = web::Json(Teacher {
+            id: 1,
+            name: "Updated teacher".into(),
+            picture_url: "http://example.com/pic.png".into(),
+            profile: "An updated teacher profile".into(),
+        });
+
+        let params: web::Path<(i32)> = web::Path::from((1));
+        let resp = update_teacher(app_state, params, updated_teacher)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ignore]
+    #[actix_rt::test]
+    async fn delete_teacher_test() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let params: web::Path<(i32)> = web::Path::from((1));
+        let resp = delete_teacher(app_state, params).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn get_all_teachers_success() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let resp = get_all_teachers(app_state).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn get_teacher_detail_success() {
+        dotenv().ok();
+        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set.");
+
+        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+
+        let app_state: web::Data<AppState> = web::Data::new(AppState {
+            health_check_response: "".to_string(),
+            visit_count: Mutex::new(0),
+            postgres: db_pool,
+            teacher_sessions: Mutex::new(HashMap::new()),
+        });
+
+        let params: web::Path<(i32)> = web::Path::from((1));
+        let resp = get_teacher_detail(app_state, params).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
     }
 }